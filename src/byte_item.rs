@@ -0,0 +1,141 @@
+use crate::ExitStatus;
+use bytes::Bytes;
+use std::{fmt, io, time::Duration};
+
+/// [`crate::Process`] byte-chunk stream output.
+///
+/// Mirrors [`crate::ProcessItem`] but yields raw chunks as they arrive
+/// instead of buffering until a newline, so partial lines and `\r`-based
+/// progress updates aren't lost or collapsed. Useful for binary output,
+/// incremental progress bars, and prompts that never emit a newline.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, PartialEq, Eq)]
+pub enum ByteProcessItem {
+    /// A raw stdout chunk as it arrived.
+    Output(Bytes),
+    /// A raw stderr chunk as it arrived, or an internal error message.
+    Error(Bytes),
+    /// Indication that the process exited, carrying its [`ExitStatus`]
+    Exit(ExitStatus),
+    /// Indication that the process exceeded its configured timeout and was killed
+    Timeout(Duration),
+}
+
+impl fmt::Debug for ByteProcessItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Output(out) => write!(f, "[Output] {out:?}"),
+            Self::Error(err) => write!(f, "[Error] {err:?}"),
+            Self::Exit(status) => write!(f, "[Exit] {status}"),
+            Self::Timeout(duration) => write!(f, "[Timeout] {duration:?}"),
+        }
+    }
+}
+
+impl From<(bool, io::Result<Bytes>)> for ByteProcessItem {
+    fn from((is_stdout, chunk): (bool, io::Result<Bytes>)) -> Self {
+        match chunk {
+            Ok(bytes) if is_stdout => Self::Output(bytes),
+            Ok(bytes) => Self::Error(bytes),
+            Err(e) => Self::Error(Bytes::from(e.to_string().into_bytes())),
+        }
+    }
+}
+
+impl ByteProcessItem {
+    /// Returns `true` if the process item is [`Output`].
+    ///
+    /// [`Output`]: ByteProcessItem::Output
+    #[must_use]
+    pub const fn is_output(&self) -> bool {
+        matches!(self, Self::Output(..))
+    }
+
+    /// Returns `true` if the process item is [`Error`].
+    ///
+    /// [`Error`]: ByteProcessItem::Error
+    #[must_use]
+    pub const fn is_error(&self) -> bool {
+        matches!(self, Self::Error(..))
+    }
+
+    /// Returns `true` if the process item is [`Exit`].
+    ///
+    /// [`Exit`]: ByteProcessItem::Exit
+    #[must_use]
+    pub const fn is_exit(&self) -> bool {
+        matches!(self, Self::Exit(..))
+    }
+
+    /// Returns Some(`true`) if the process item is [`Exit`] and it exited
+    /// normally with code 0; `Some(false)` for a non-zero code or a signal
+    /// death, `None` if the item isn't [`Exit`] or neither is known
+    ///
+    /// [`Exit`]: ByteProcessItem::Exit
+    #[must_use]
+    pub fn is_success(&self) -> Option<bool> {
+        self.as_exit().and_then(ExitStatus::is_success)
+    }
+
+    /// Return the [`ExitStatus`] if [`ByteProcessItem`] is [`ByteProcessItem::Exit`]
+    #[must_use]
+    pub const fn as_exit(&self) -> Option<&ExitStatus> {
+        if let Self::Exit(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Return the exit code if [`ByteProcessItem`] is [`ByteProcessItem::Exit`] and
+    /// the process exited normally (as opposed to being killed by a signal)
+    #[must_use]
+    pub fn exit_code(&self) -> Option<i32> {
+        self.as_exit().and_then(|status| status.code)
+    }
+
+    /// Return the signal that terminated the process, if [`ByteProcessItem`] is
+    /// [`ByteProcessItem::Exit`] and it was killed by one
+    #[must_use]
+    pub fn terminated_by_signal(&self) -> Option<i32> {
+        self.as_exit().and_then(|status| status.signal)
+    }
+
+    /// Return inner reference [`Bytes`] value if [`ByteProcessItem`] is [`ByteProcessItem::Error`]
+    #[must_use]
+    pub const fn as_error(&self) -> Option<&Bytes> {
+        if let Self::Error(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Return inner reference [`Bytes`] value if [`ByteProcessItem`] is [`ByteProcessItem::Output`]
+    #[must_use]
+    pub const fn as_output(&self) -> Option<&Bytes> {
+        if let Self::Output(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the process item is [`Timeout`].
+    ///
+    /// [`Timeout`]: ByteProcessItem::Timeout
+    #[must_use]
+    pub const fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout(..))
+    }
+
+    /// Return the configured [`Duration`] if [`ByteProcessItem`] is [`ByteProcessItem::Timeout`]
+    #[must_use]
+    pub const fn as_timeout(&self) -> Option<&Duration> {
+        if let Self::Timeout(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+}
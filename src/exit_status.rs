@@ -0,0 +1,59 @@
+use std::fmt;
+
+/// Structured exit status of a spawned process, distinguishing a normal
+/// exit code from termination by signal.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExitStatus {
+    /// The process's exit code, if it exited on its own.
+    pub code: Option<i32>,
+    /// The signal that terminated the process, if it was killed by one.
+    pub signal: Option<i32>,
+}
+
+impl ExitStatus {
+    pub(crate) fn from_std(status: std::process::ExitStatus) -> Self {
+        Self {
+            code: status.code(),
+            #[cfg(unix)]
+            signal: {
+                use std::os::unix::process::ExitStatusExt;
+                status.signal()
+            },
+            #[cfg(not(unix))]
+            signal: None,
+        }
+    }
+
+    /// An [`ExitStatus`] denoting the process was killed with `signal`,
+    /// used when the exit status itself couldn't be observed (after
+    /// [`crate::Process::abort`] or an escalated graceful abort).
+    pub(crate) fn killed(signal: i32) -> Self {
+        Self {
+            code: None,
+            signal: Some(signal),
+        }
+    }
+
+    /// Returns `Some(true)` only if the process exited normally with code 0,
+    /// `Some(false)` if it exited with a non-zero code or was killed by a
+    /// signal, and `None` if neither is known.
+    #[must_use]
+    pub fn is_success(&self) -> Option<bool> {
+        match (self.code, self.signal) {
+            (Some(code), _) => Some(code == 0),
+            (None, Some(_)) => Some(false),
+            (None, None) => None,
+        }
+    }
+}
+
+impl fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.code, self.signal) {
+            (Some(code), _) => write!(f, "{code}"),
+            (None, Some(signal)) => write!(f, "signal {signal}"),
+            (None, None) => write!(f, "unknown"),
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use std::{fmt, io, ops::Deref};
+use crate::ExitStatus;
+use std::{fmt, io, ops::Deref, time::Duration};
 
 /// [`crate::Process`] stream output
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -8,8 +9,10 @@ pub enum ProcessItem {
     Output(String),
     /// A stderr chunk printed by the process or internal error message
     Error(String),
-    /// Indication that the process exit successful
-    Exit(String),
+    /// Indication that the process exited, carrying its [`ExitStatus`]
+    Exit(ExitStatus),
+    /// Indication that the process exceeded its configured timeout and was killed
+    Timeout(Duration),
 }
 
 impl Deref for ProcessItem {
@@ -19,14 +22,21 @@ impl Deref for ProcessItem {
         match self {
             Self::Output(s) => s,
             Self::Error(s) => s,
-            Self::Exit(s) => s,
+            // `Exit`/`Timeout` don't carry a `String`; use `Display`/`as_exit`/
+            // `as_timeout` to inspect them, this is just here to keep the
+            // `Deref` convenience usable.
+            Self::Exit(_) | Self::Timeout(_) => "",
         }
     }
 }
 
 impl fmt::Display for ProcessItem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.deref().fmt(f)
+        match self {
+            Self::Exit(status) => status.fmt(f),
+            Self::Timeout(duration) => write!(f, "timed out after {duration:?}"),
+            _ => self.deref().fmt(f),
+        }
     }
 }
 
@@ -35,7 +45,8 @@ impl fmt::Debug for ProcessItem {
         match self {
             Self::Output(out) => write!(f, "[Output] {out}"),
             Self::Error(err) => write!(f, "[Error] {err}"),
-            Self::Exit(code) => write!(f, "[Exit] {code}"),
+            Self::Exit(status) => write!(f, "[Exit] {status}"),
+            Self::Timeout(duration) => write!(f, "[Timeout] {duration:?}"),
         }
     }
 }
@@ -74,17 +85,19 @@ impl ProcessItem {
         matches!(self, Self::Exit(..))
     }
 
-    /// Returns Some(`true`) if the process item is [`Exit`] and returned 0
+    /// Returns Some(`true`) if the process item is [`Exit`] and it exited
+    /// normally with code 0; `Some(false)` for a non-zero code or a signal
+    /// death, `None` if the item isn't [`Exit`] or neither is known
     ///
     /// [`Exit`]: ProcessItem::Exit
     #[must_use]
     pub fn is_success(&self) -> Option<bool> {
-        self.as_exit().map(|s| s.trim() == "0")
+        self.as_exit().and_then(ExitStatus::is_success)
     }
 
-    /// Return exit code if [`ProcessItem`] is [`ProcessItem::Exit`]
+    /// Return the [`ExitStatus`] if [`ProcessItem`] is [`ProcessItem::Exit`]
     #[must_use]
-    pub const fn as_exit(&self) -> Option<&String> {
+    pub const fn as_exit(&self) -> Option<&ExitStatus> {
         if let Self::Exit(v) = self {
             Some(v)
         } else {
@@ -92,6 +105,20 @@ impl ProcessItem {
         }
     }
 
+    /// Return the exit code if [`ProcessItem`] is [`ProcessItem::Exit`] and
+    /// the process exited normally (as opposed to being killed by a signal)
+    #[must_use]
+    pub fn exit_code(&self) -> Option<i32> {
+        self.as_exit().and_then(|status| status.code)
+    }
+
+    /// Return the signal that terminated the process, if [`ProcessItem`] is
+    /// [`ProcessItem::Exit`] and it was killed by one
+    #[must_use]
+    pub fn terminated_by_signal(&self) -> Option<i32> {
+        self.as_exit().and_then(|status| status.signal)
+    }
+
     /// Return inner reference [`String`] value if [`ProcessItem`] is [`ProcessItem::Error`]
     #[must_use]
     pub const fn as_error(&self) -> Option<&String> {
@@ -111,4 +138,22 @@ impl ProcessItem {
             None
         }
     }
+
+    /// Returns `true` if the process item is [`Timeout`].
+    ///
+    /// [`Timeout`]: ProcessItem::Timeout
+    #[must_use]
+    pub const fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout(..))
+    }
+
+    /// Return the configured [`Duration`] if [`ProcessItem`] is [`ProcessItem::Timeout`]
+    #[must_use]
+    pub const fn as_timeout(&self) -> Option<&Duration> {
+        if let Self::Timeout(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
 }
@@ -8,18 +8,34 @@
 /// Alias for a stream of process items
 pub type ProcessStream = Pin<Box<dyn Stream<Item = ProcessItem> + Send>>;
 
+/// Alias for a stream of byte-chunk process items
+pub type ByteProcessStream = Pin<Box<dyn Stream<Item = ByteProcessItem> + Send>>;
+
+/// How long [`Process::graceful_abort`] waits after signalling before
+/// escalating to a hard kill, unless overridden.
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// The signal number used to record an [`ExitStatus`] when a process was
+/// killed outright (abort/escalated graceful abort) rather than observed
+/// exiting on its own. A timeout yields a `Timeout` item instead.
+const SIGKILL: i32 = 9;
+
 pub use async_stream::stream;
+use bytes::Bytes;
 use io::Result;
 use std::{
     ffi::OsStr,
+    future::Future,
     io,
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
     pin::Pin,
     process::Stdio,
     sync::Arc,
+    time::Duration,
 };
 use tap::Pipe;
+use tokio_util::io::ReaderStream;
 use {
     tokio::{
         io::{AsyncBufReadExt, AsyncRead, BufReader},
@@ -29,14 +45,146 @@ use {
     tokio_stream::wrappers::LinesStream,
 };
 
+mod byte_item;
+mod exit_status;
 mod item;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod pty;
+mod signal;
 pub use async_trait::async_trait;
+pub use byte_item::ByteProcessItem;
+pub use exit_status::ExitStatus;
 pub use futures::Stream;
 pub use futures::StreamExt;
 pub use futures::TryStreamExt;
 pub use item::ProcessItem;
+pub use pty::{PtyHandle, PtySize};
+pub use signal::Signal;
 pub use tokio_stream;
 
+/// Lets [`run_process_loop`] construct `Error`/`Exit`/`Timeout` items
+/// generically over [`ProcessItem`] and [`ByteProcessItem`], which only
+/// diverge in how they carry output/errors (`String` vs [`Bytes`]).
+trait StreamItem: Sized {
+    /// Build the item reporting an internal/stderr error message.
+    fn error(message: String) -> Self;
+    /// Build the item reporting the process's [`ExitStatus`].
+    fn exit(status: ExitStatus) -> Self;
+    /// Build the item reporting that the process was killed for exceeding its timeout.
+    fn timeout(duration: Duration) -> Self;
+}
+
+impl StreamItem for ProcessItem {
+    fn error(message: String) -> Self {
+        Self::Error(message)
+    }
+
+    fn exit(status: ExitStatus) -> Self {
+        Self::Exit(status)
+    }
+
+    fn timeout(duration: Duration) -> Self {
+        Self::Timeout(duration)
+    }
+}
+
+impl StreamItem for ByteProcessItem {
+    fn error(message: String) -> Self {
+        Self::Error(message.into_bytes().into())
+    }
+
+    fn exit(status: ExitStatus) -> Self {
+        Self::Exit(status)
+    }
+
+    fn timeout(duration: Duration) -> Self {
+        Self::Timeout(duration)
+    }
+}
+
+/// Shared `child.wait()`/abort/timeout/graceful-abort state machine behind
+/// [`ProcessExt::_spawn_and_stream`], [`ProcessExt::_spawn_and_stream_bytes`]
+/// and [`Process::spawn_and_stream_pty`], generic over the yielded item
+/// type so the spawn paths can't drift out of sync with each other.
+fn run_process_loop<T>(
+    mut child: tokio::process::Child,
+    mut std_stream: impl Stream<Item = T> + Unpin + Send + 'static,
+    abort: Arc<Notify>,
+    graceful_abort: Arc<Notify>,
+    signal: Signal,
+    grace_period: Option<Duration>,
+    timeout: Option<Duration>,
+    #[cfg(feature = "metrics")] mut metrics_guard: metrics::MetricsGuard,
+) -> Pin<Box<dyn Stream<Item = T> + Send>>
+where
+    T: StreamItem + Send + 'static,
+{
+    let sleep = async move {
+        match timeout {
+            Some(duration) => tokio::time::sleep(duration).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(sleep);
+
+    let mut escalate: Pin<Box<dyn Future<Output = ()> + Send>> = Box::pin(std::future::pending());
+
+    stream! {
+        loop {
+            tokio::select! {
+                Some(output) = std_stream.next() => yield output,
+                status = child.wait() => match status {
+                    Err(err) => yield T::error(err.to_string()),
+                    Ok(status) => {
+                        #[cfg(feature = "metrics")]
+                        metrics_guard.disarm();
+                        yield T::exit(ExitStatus::from_std(status));
+                        break;
+                    }
+                },
+                _ = abort.notified() => {
+                    match child.start_kill() {
+                        Ok(()) => yield T::exit(ExitStatus::killed(SIGKILL)),
+                        Err(err) => yield T::error(format!("abort Process Error: {err}")),
+                    };
+                    break;
+                }
+                _ = &mut sleep => {
+                    match child.start_kill() {
+                        Ok(()) => yield T::timeout(timeout.unwrap_or_default()),
+                        Err(err) => yield T::error(format!("timeout Process Error: {err}")),
+                    };
+                    break;
+                }
+                _ = graceful_abort.notified() => {
+                    match signal::send(&child, signal) {
+                        Ok(()) => escalate = Box::pin(tokio::time::sleep(
+                            grace_period.unwrap_or(DEFAULT_GRACE_PERIOD),
+                        )),
+                        Err(err) => {
+                            yield T::error(format!("graceful_abort Process Error: {err}"));
+                            match child.start_kill() {
+                                Ok(()) => yield T::exit(ExitStatus::killed(SIGKILL)),
+                                Err(err) => yield T::error(format!("abort Process Error: {err}")),
+                            };
+                            break;
+                        }
+                    }
+                }
+                _ = &mut escalate => {
+                    match child.start_kill() {
+                        Ok(()) => yield T::exit(ExitStatus::killed(SIGKILL)),
+                        Err(err) => yield T::error(format!("graceful_abort Process Error: {err}")),
+                    };
+                    break;
+                }
+            }
+        }
+    }
+    .boxed()
+}
+
 #[async_trait]
 /// ProcessExt trait that needs to be implemented to make something streamable
 pub trait ProcessExt {
@@ -64,52 +212,119 @@ pub trait ProcessExt {
     /// Spawn and stream process (avoid custom implementation, use spawn_and_stream instead)
     fn _spawn_and_stream(&mut self) -> Result<ProcessStream> {
         let abort = Arc::new(Notify::new());
+        let graceful_abort = Arc::new(Notify::new());
+        let timeout = self.get_timeout();
+        let signal = self.get_signal();
+        let grace_period = self.get_grace_period();
 
         let mut child = self.command().spawn()?;
 
+        #[cfg(feature = "metrics")]
+        let metrics_guard = {
+            let program = self
+                .get_command()
+                .as_std()
+                .get_program()
+                .to_string_lossy()
+                .into_owned();
+            crate::metrics::MetricsGuard::guard(program)
+        };
+
         let stdout = child.stdout.take().unwrap();
         let stderr = child.stderr.take().unwrap();
 
         self.set_child_stdin(child.stdin.take());
         self.set_aborter(Some(abort.clone()));
+        self.set_graceful_aborter(Some(graceful_abort.clone()));
 
         let stdout_stream = into_stream(stdout, true);
         let stderr_stream = into_stream(stderr, false);
-        let mut std_stream = tokio_stream::StreamExt::merge(stdout_stream, stderr_stream);
-
-        let stream = stream! {
-            loop {
-                use ProcessItem::*;
-                tokio::select! {
-                    Some(output) = std_stream.next() => yield output,
-                    status = child.wait() => match status {
-                        Err(err) => yield Error(err.to_string()),
-                        Ok(status) => {
-                            match status.code() {
-                                Some(code) => yield Exit(format!("{code}")),
-                                None => yield Error("Unable to get exit code".into()),
-                            }
-                            break;
+        let std_stream = tokio_stream::StreamExt::merge(stdout_stream, stderr_stream);
+
+        Ok(run_process_loop(
+            child,
+            std_stream,
+            abort,
+            graceful_abort,
+            signal,
+            grace_period,
+            timeout,
+            #[cfg(feature = "metrics")]
+            metrics_guard,
+        ))
+    }
+
+    /// Spawn and stream process as raw byte chunks instead of decoded lines,
+    /// preserving partial lines and `\r`-based progress updates
+    fn spawn_and_stream_bytes(&mut self) -> Result<ByteProcessStream> {
+        self._spawn_and_stream_bytes()
+    }
+
+    /// Spawn and stream process as raw byte chunks (avoid custom
+    /// implementation, use spawn_and_stream_bytes instead)
+    fn _spawn_and_stream_bytes(&mut self) -> Result<ByteProcessStream> {
+        let abort = Arc::new(Notify::new());
+        let graceful_abort = Arc::new(Notify::new());
+        let timeout = self.get_timeout();
+        let signal = self.get_signal();
+        let grace_period = self.get_grace_period();
 
-                        }
-                    },
-                    _ = abort.notified() => {
-                        match child.start_kill() {
-                            Ok(()) => yield Exit("0".into()),
-                            Err(err) => yield Error(format!("abort Process Error: {err}")),
-                        };
-                        break;
-                    }
-                }
-            }
+        let mut child = self.command().spawn()?;
+
+        #[cfg(feature = "metrics")]
+        let metrics_guard = {
+            let program = self
+                .get_command()
+                .as_std()
+                .get_program()
+                .to_string_lossy()
+                .into_owned();
+            crate::metrics::MetricsGuard::guard(program)
         };
 
-        Ok(stream.boxed())
+        let stdout = child.stdout.take().unwrap();
+        let stderr = child.stderr.take().unwrap();
+
+        self.set_child_stdin(child.stdin.take());
+        self.set_aborter(Some(abort.clone()));
+        self.set_graceful_aborter(Some(graceful_abort.clone()));
+
+        let stdout_stream = into_byte_stream(stdout, true);
+        let stderr_stream = into_byte_stream(stderr, false);
+        let std_stream = tokio_stream::StreamExt::merge(stdout_stream, stderr_stream);
+
+        Ok(run_process_loop(
+            child,
+            std_stream,
+            abort,
+            graceful_abort,
+            signal,
+            grace_period,
+            timeout,
+            #[cfg(feature = "metrics")]
+            metrics_guard,
+        ))
     }
     /// Get a notifier that can be used to abort the process
     fn aborter(&self) -> Option<Arc<Notify>>;
     /// Set the notifier that should be used to abort the process
     fn set_aborter(&mut self, aborter: Option<Arc<Notify>>);
+    /// Get a notifier that can be used to gracefully abort the process
+    fn graceful_aborter(&self) -> Option<Arc<Notify>>;
+    /// Set the notifier that should be used to gracefully abort the process
+    fn set_graceful_aborter(&mut self, aborter: Option<Arc<Notify>>);
+    /// Get the signal a graceful abort sends before escalating to a hard kill
+    fn get_signal(&mut self) -> Signal {
+        signal::default_signal()
+    }
+    /// Get how long a graceful abort waits after signalling before escalating
+    fn get_grace_period(&mut self) -> Option<Duration> {
+        None
+    }
+    /// Get the configured timeout after which the process is killed
+    fn get_timeout(&mut self) -> Option<Duration> {
+        None
+    }
     /// Get process stdin
     fn take_stdin(&mut self) -> Option<ChildStdin> {
         None
@@ -138,6 +353,12 @@ pub struct Process {
     set_stdout: Option<Stdio>,
     set_stderr: Option<Stdio>,
     abort: Option<Arc<Notify>>,
+    graceful_abort: Option<Arc<Notify>>,
+    signal: Signal,
+    grace_period: Option<Duration>,
+    timeout: Option<Duration>,
+    pty: bool,
+    pty_size: PtySize,
 }
 
 impl ProcessExt for Process {
@@ -153,6 +374,26 @@ impl ProcessExt for Process {
         self.abort = aborter
     }
 
+    fn graceful_aborter(&self) -> Option<Arc<Notify>> {
+        self.graceful_abort.clone()
+    }
+
+    fn set_graceful_aborter(&mut self, aborter: Option<Arc<Notify>>) {
+        self.graceful_abort = aborter
+    }
+
+    fn get_signal(&mut self) -> Signal {
+        self.signal
+    }
+
+    fn get_grace_period(&mut self) -> Option<Duration> {
+        self.grace_period
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        self.timeout
+    }
+
     fn take_stdin(&mut self) -> Option<ChildStdin> {
         self.stdin.take()
     }
@@ -184,6 +425,12 @@ impl Process {
             set_stderr: Some(Stdio::piped()),
             stdin: None,
             abort: None,
+            graceful_abort: None,
+            signal: signal::default_signal(),
+            grace_period: None,
+            timeout: None,
+            pty: false,
+            pty_size: PtySize::default(),
         }
     }
 
@@ -202,6 +449,99 @@ impl Process {
         self.set_stderr = stderr.into();
     }
 
+    /// Set a timeout after which the process is killed and a [`ProcessItem::Timeout`]
+    /// is yielded instead of waiting for it to exit on its own.
+    pub fn timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    /// Set the signal sent by [`Process::graceful_abort`] before it escalates
+    /// to a hard kill. Defaults to `SIGTERM`.
+    pub fn signal(&mut self, signal: Signal) {
+        self.signal = signal;
+    }
+
+    /// Set how long [`Process::graceful_abort`] waits after signalling before
+    /// escalating to a hard kill. Defaults to 10 seconds.
+    pub fn grace_period(&mut self, period: Duration) {
+        self.grace_period = Some(period);
+    }
+
+    /// Send the configured signal (`SIGTERM` by default), giving the process
+    /// a chance to clean up, and escalate to a hard kill only if it hasn't
+    /// exited after the grace period.
+    pub fn graceful_abort(&self) {
+        self.graceful_aborter().map(|k| k.notify_waiters());
+    }
+
+    /// Spawn the process attached to a pseudo-terminal instead of plain
+    /// pipes, when `enabled`. Useful for programs that detect or require a
+    /// tty, such as progress bars, colorized CLIs, or REPLs.
+    pub fn pty(&mut self, enabled: bool) {
+        self.pty = enabled;
+    }
+
+    /// Set the initial PTY window size used when [`Process::pty`] is enabled.
+    pub fn pty_size(&mut self, rows: u16, cols: u16) {
+        self.pty_size = PtySize { rows, cols };
+    }
+
+    /// Spawn the process over a pseudo-terminal and stream its combined
+    /// terminal output, returning a [`PtyHandle`] to resize the window and
+    /// (via [`PtyHandle::take_writer`]) drive the child's stdin.
+    ///
+    /// Shares the same abort, graceful abort, timeout and (with the
+    /// `metrics` feature) metrics machinery as [`Self::spawn_and_stream`].
+    ///
+    /// Requires [`Process::pty`] to have been enabled, or this returns an
+    /// error; on non-unix targets a PTY-enabled spawn always returns an
+    /// unsupported error.
+    pub fn spawn_and_stream_pty(&mut self) -> Result<(ProcessStream, PtyHandle)> {
+        if !self.pty {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Process::pty(true) must be called before spawn_and_stream_pty",
+            ));
+        }
+
+        let abort = Arc::new(Notify::new());
+        let graceful_abort = Arc::new(Notify::new());
+        let signal = self.get_signal();
+        let grace_period = self.get_grace_period();
+        let timeout = self.get_timeout();
+
+        let (child, std_stream, handle) =
+            pty::spawn_and_stream_pty(&mut self.inner, self.pty_size)?;
+
+        #[cfg(feature = "metrics")]
+        let metrics_guard = {
+            let program = self
+                .get_command()
+                .as_std()
+                .get_program()
+                .to_string_lossy()
+                .into_owned();
+            crate::metrics::MetricsGuard::guard(program)
+        };
+
+        self.set_aborter(Some(abort.clone()));
+        self.set_graceful_aborter(Some(graceful_abort.clone()));
+
+        let stream = run_process_loop(
+            child,
+            std_stream,
+            abort,
+            graceful_abort,
+            signal,
+            grace_period,
+            timeout,
+            #[cfg(feature = "metrics")]
+            metrics_guard,
+        );
+
+        Ok((stream, handle))
+    }
+
     /// Abort the process
     pub fn abort(&self) {
         self.aborter().map(|k| k.notify_waiters());
@@ -231,6 +571,12 @@ impl From<Command> for Process {
             set_stdout: Some(Stdio::piped()),
             set_stderr: Some(Stdio::piped()),
             abort: None,
+            graceful_abort: None,
+            signal: signal::default_signal(),
+            grace_period: None,
+            timeout: None,
+            pty: false,
+            pty_size: PtySize::default(),
         }
     }
 }
@@ -278,6 +624,16 @@ where
         .map(move |v| T::from((is_stdout, v)))
 }
 
+/// Convert std_stream to a stream of raw byte chunks of T, without waiting
+/// for a full line
+pub fn into_byte_stream<T, R>(std: R, is_stdout: bool) -> impl Stream<Item = T>
+where
+    T: From<(bool, Result<Bytes>)>,
+    R: AsyncRead,
+{
+    ReaderStream::new(std).map(move |v| T::from((is_stdout, v)))
+}
+
 #[cfg(test)]
 mod tests {
     use tokio::io::AsyncWriteExt;
@@ -365,13 +721,90 @@ mod tests {
         let items = vec![
             Output("Hello".into()),
             Error("XXXXXXXXXX".into()),
-            Exit("0".into()),
+            Exit(ExitStatus {
+                code: Some(0),
+                signal: None,
+            }),
         ];
         for item in items {
             println!("{:?}", item.as_bytes())
         }
     }
 
+    #[tokio::test]
+    async fn test_timeout() -> Result<()> {
+        let mut process = Process::new("sh");
+        process.args(["-c", "sleep 5"]);
+        process.timeout(std::time::Duration::from_millis(50));
+
+        let outputs = process.spawn_and_stream()?.collect::<Vec<_>>().await;
+
+        assert!(outputs.iter().any(ProcessItem::is_timeout));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_graceful_abort_escalates_to_sigkill() -> Result<()> {
+        let mut process = Process::new("sh");
+        process.args(["-c", "trap '' TERM; sleep 5"]);
+        process.grace_period(std::time::Duration::from_millis(50));
+
+        // Poll the stream in the background: `graceful_abort` only reaches a
+        // stream that is already waiting on its notifier.
+        let stream = process.spawn_and_stream()?;
+        let collector = tokio::spawn(stream.collect::<Vec<_>>());
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        process.graceful_abort();
+
+        let outputs = collector.await?;
+
+        let exit = outputs.iter().find_map(ProcessItem::as_exit).unwrap();
+        assert_eq!(exit.signal, Some(9));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_byte_stream_preserves_partial_line() -> Result<()> {
+        let mut process = Process::new("printf");
+        process.args(["no trailing newline"]);
+
+        let outputs = process.spawn_and_stream_bytes()?.collect::<Vec<_>>().await;
+
+        let stdout = outputs
+            .iter()
+            .filter_map(ByteProcessItem::as_output)
+            .flat_map(|chunk| chunk.iter().copied())
+            .collect::<Vec<u8>>();
+
+        assert_eq!(stdout, b"no trailing newline");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_exit_status_code() -> Result<()> {
+        let mut process = Process::new("false");
+        let outputs = process.spawn_and_stream()?.collect::<Vec<_>>().await;
+
+        let exit = outputs.iter().find_map(ProcessItem::as_exit).unwrap();
+        assert_eq!(exit.is_success(), Some(false));
+        assert_eq!(exit.code, Some(1));
+        assert_eq!(exit.signal, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_exit_status_signal() -> Result<()> {
+        let mut process = Process::new("sh");
+        process.args(["-c", "kill -9 $$"]);
+        let outputs = process.spawn_and_stream()?.collect::<Vec<_>>().await;
+
+        let exit = outputs.iter().find_map(ProcessItem::as_exit).unwrap();
+        assert_eq!(exit.is_success(), Some(false));
+        assert_eq!(exit.code, None);
+        assert_eq!(exit.signal, Some(9));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn communicate_with_running_process() -> Result<()> {
         let mut process: Process = Process::new("sort");
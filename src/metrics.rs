@@ -0,0 +1,54 @@
+//! Optional telemetry for spawned processes, emitted via the [`metrics`] crate
+//! when the `metrics` feature is enabled.
+use std::time::Instant;
+
+/// RAII guard that records `process.start`/`process.duration`/`process.end`
+/// metrics for a single spawned process, tagged with its program name.
+///
+/// The guard is armed at spawn time and disarmed once the process exits on
+/// its own. Its [`Drop`] impl always emits the duration histogram and the
+/// end counter, tagging whether the process completed normally or was
+/// killed/aborted (or the stream was dropped early, e.g. on panic).
+pub(crate) struct MetricsGuard {
+    program: String,
+    start: Instant,
+    armed: bool,
+}
+
+impl MetricsGuard {
+    /// Start tracking a freshly spawned process, incrementing `process.start`.
+    pub(crate) fn guard(program: String) -> Self {
+        metrics::counter!("process.start", "program" => program.clone()).increment(1);
+
+        Self {
+            program,
+            start: Instant::now(),
+            armed: true,
+        }
+    }
+
+    /// Mark the process as having completed normally.
+    pub(crate) fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for MetricsGuard {
+    fn drop(&mut self) {
+        let completed = (!self.armed).to_string();
+
+        metrics::histogram!(
+            "process.duration",
+            "program" => self.program.clone(),
+            "completed" => completed.clone(),
+        )
+        .record(self.start.elapsed().as_secs_f64());
+
+        metrics::counter!(
+            "process.end",
+            "program" => self.program.clone(),
+            "completed" => completed,
+        )
+        .increment(1);
+    }
+}
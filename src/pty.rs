@@ -0,0 +1,279 @@
+//! PTY-backed process spawning, for interactive or tty-detecting programs
+//! that change behavior (or refuse to run at all) when their stdout is a
+//! plain pipe rather than a terminal.
+
+/// Initial terminal window size requested for a PTY-backed process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PtySize {
+    /// Number of terminal rows.
+    pub rows: u16,
+    /// Number of terminal columns.
+    pub cols: u16,
+}
+
+impl Default for PtySize {
+    fn default() -> Self {
+        Self { rows: 24, cols: 80 }
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::PtySize;
+    use crate::{into_stream, ProcessItem, ProcessStream};
+    use futures::StreamExt;
+    use nix::libc;
+    use nix::pty::{openpty, Winsize};
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+    use std::os::unix::process::CommandExt;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{ready, Context, Poll};
+    use tokio::io::unix::AsyncFd;
+    use tokio::io::{split, AsyncRead, AsyncWrite, ReadBuf, WriteHalf};
+    use tokio::process::Command;
+
+    /// Writer half of a PTY master, for driving interactive programs
+    /// (REPLs, prompts) that read from their controlling terminal.
+    pub type PtyWriter = Pin<Box<dyn AsyncWrite + Send>>;
+
+    /// A PTY master fd shared between the [`PtyMaster`] reader/writer living
+    /// in the stream and the [`PtyHandle`] returned alongside it, so the fd
+    /// stays open (and can't be reused by the OS for something unrelated)
+    /// until both are dropped.
+    #[derive(Clone)]
+    struct SharedFd(Arc<OwnedFd>);
+
+    impl AsRawFd for SharedFd {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0.as_raw_fd()
+        }
+    }
+
+    /// Async reader/writer over a PTY master file descriptor.
+    struct PtyMaster(AsyncFd<SharedFd>);
+
+    impl AsRawFd for PtyMaster {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0.get_ref().as_raw_fd()
+        }
+    }
+
+    impl AsyncRead for PtyMaster {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            loop {
+                let mut guard = ready!(self.0.poll_read_ready(cx))?;
+                let unfilled = buf.initialize_unfilled();
+                let result = guard.try_io(|fd| {
+                    // SAFETY: `unfilled` is a valid, properly sized buffer
+                    // for the duration of this call.
+                    let n = unsafe {
+                        libc::read(fd.as_raw_fd(), unfilled.as_mut_ptr().cast(), unfilled.len())
+                    };
+                    if n < 0 {
+                        let err = io::Error::last_os_error();
+                        // The kernel reports the slave side hanging up as
+                        // EIO (and sometimes ENXIO), not a 0-byte read; treat
+                        // both as a normal EOF instead of a stream error.
+                        match err.raw_os_error() {
+                            Some(libc::EIO) | Some(libc::ENXIO) => Ok(0),
+                            _ => Err(err),
+                        }
+                    } else {
+                        Ok(n as usize)
+                    }
+                });
+                match result {
+                    Ok(Ok(n)) => {
+                        buf.advance(n);
+                        return Poll::Ready(Ok(()));
+                    }
+                    Ok(Err(err)) => return Poll::Ready(Err(err)),
+                    Err(_would_block) => continue,
+                }
+            }
+        }
+    }
+
+    impl AsyncWrite for PtyMaster {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            loop {
+                let mut guard = ready!(self.0.poll_write_ready(cx))?;
+                let result = guard.try_io(|fd| {
+                    // SAFETY: `buf` is a valid, properly sized buffer for
+                    // the duration of this call.
+                    let n = unsafe { libc::write(fd.as_raw_fd(), buf.as_ptr().cast(), buf.len()) };
+                    if n < 0 {
+                        Err(io::Error::last_os_error())
+                    } else {
+                        Ok(n as usize)
+                    }
+                });
+                match result {
+                    Ok(result) => return Poll::Ready(result),
+                    Err(_would_block) => continue,
+                }
+            }
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn winsize(size: PtySize) -> Winsize {
+        Winsize {
+            ws_row: size.rows,
+            ws_col: size.cols,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        }
+    }
+
+    /// Handle to a running PTY-backed process.
+    ///
+    /// Kept alongside the [`ProcessStream`] returned by
+    /// [`super::spawn_and_stream_pty`] so callers can resize the child's
+    /// window (e.g. in response to `SIGWINCH`). Shares ownership of the PTY
+    /// master fd with the stream, so the fd stays valid (and can't be
+    /// reassigned to an unrelated file/socket by the OS) for as long as
+    /// either half is still alive.
+    pub struct PtyHandle {
+        master: Arc<OwnedFd>,
+        writer: Option<WriteHalf<PtyMaster>>,
+    }
+
+    impl PtyHandle {
+        /// Resize the PTY's window, so `SIGWINCH`-aware children can reflow.
+        pub fn resize(&self, rows: u16, cols: u16) -> io::Result<()> {
+            let size = winsize(PtySize { rows, cols });
+            // SAFETY: `TIOCSWINSZ` only reads through the pointer it's given;
+            // `self.master` is kept open by the shared `Arc<OwnedFd>`.
+            let ret = unsafe { libc::ioctl(self.master.as_raw_fd(), libc::TIOCSWINSZ, &size) };
+            if ret == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Take the writer half of the PTY master, for sending input to an
+        /// interactive child (a REPL, a prompt) that reads from its
+        /// controlling terminal. Returns `None` if already taken.
+        pub fn take_writer(&mut self) -> Option<PtyWriter> {
+            self.writer.take().map(|writer| Box::pin(writer) as _)
+        }
+    }
+
+    /// Open a PTY and spawn `command` attached to it, returning the raw
+    /// child (so the caller can drive its own `wait()`/abort/timeout state
+    /// machine, the same as the plain-pipe spawn paths) together with a
+    /// stream of the combined terminal output and a [`PtyHandle`].
+    pub(crate) fn spawn_and_stream_pty(
+        command: &mut Command,
+        size: PtySize,
+    ) -> io::Result<(tokio::process::Child, ProcessStream, PtyHandle)> {
+        let pty = openpty(Some(&winsize(size)), None)?;
+
+        // SAFETY: runs in the forked child before exec; only async-signal-safe
+        // calls are made, and the slave fd stays valid for the child's stdio.
+        let slave_fd = pty.slave.as_raw_fd();
+        unsafe {
+            command.stdin(std::process::Stdio::from_raw_fd(libc::dup(slave_fd)));
+            command.stdout(std::process::Stdio::from_raw_fd(libc::dup(slave_fd)));
+            command.stderr(std::process::Stdio::from_raw_fd(libc::dup(slave_fd)));
+            command.pre_exec(move || {
+                if libc::setsid() == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::ioctl(slave_fd, libc::TIOCSCTTY as _, 0) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+
+        let child = command.spawn()?;
+        drop(pty.slave);
+
+        let master_fd = Arc::new(pty.master);
+        let master = PtyMaster(AsyncFd::new(SharedFd(master_fd.clone()))?);
+        let (reader, writer) = split(master);
+        let stream = into_stream::<ProcessItem, _>(reader, true).boxed();
+
+        Ok((
+            child,
+            stream,
+            PtyHandle {
+                master: master_fd,
+                writer: Some(writer),
+            },
+        ))
+    }
+}
+
+#[cfg(unix)]
+pub(crate) use unix::spawn_and_stream_pty;
+#[cfg(unix)]
+pub use unix::{PtyHandle, PtyWriter};
+
+#[cfg(not(unix))]
+mod not_unix {
+    use super::PtySize;
+    use crate::ProcessStream;
+    use std::io;
+    use std::pin::Pin;
+    use tokio::io::AsyncWrite;
+    use tokio::process::Command;
+
+    /// Writer half of a PTY master (unsupported on this platform).
+    pub type PtyWriter = Pin<Box<dyn AsyncWrite + Send>>;
+
+    /// Handle to a running PTY-backed process (unsupported on this platform).
+    pub struct PtyHandle(());
+
+    impl PtyHandle {
+        /// Resize the PTY's window. Always fails on non-unix platforms.
+        pub fn resize(&self, _rows: u16, _cols: u16) -> io::Result<()> {
+            Err(unsupported())
+        }
+
+        /// Take the writer half of the PTY master. Always `None` on
+        /// non-unix platforms.
+        pub fn take_writer(&mut self) -> Option<PtyWriter> {
+            None
+        }
+    }
+
+    pub(crate) fn spawn_and_stream_pty(
+        _command: &mut Command,
+        _size: PtySize,
+    ) -> io::Result<(tokio::process::Child, ProcessStream, PtyHandle)> {
+        Err(unsupported())
+    }
+
+    fn unsupported() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "pty-backed spawning is only supported on unix",
+        )
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) use not_unix::spawn_and_stream_pty;
+#[cfg(not(unix))]
+pub use not_unix::{PtyHandle, PtyWriter};
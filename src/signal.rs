@@ -0,0 +1,45 @@
+//! Graceful, signal-based process termination.
+
+use std::io;
+
+#[cfg(unix)]
+pub use nix::sys::signal::Signal;
+
+/// A process signal, used by [`crate::Process::signal`] to configure what
+/// [`crate::Process::graceful_abort`] sends before escalating to `SIGKILL`.
+///
+/// This is an opaque stand-in on non-unix targets, where
+/// [`crate::Process::graceful_abort`] always falls back to a hard kill.
+#[cfg(not(unix))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signal;
+
+/// The signal [`crate::Process::graceful_abort`] sends unless overridden.
+#[cfg(unix)]
+pub(crate) fn default_signal() -> Signal {
+    Signal::SIGTERM
+}
+
+#[cfg(not(unix))]
+pub(crate) fn default_signal() -> Signal {
+    Signal
+}
+
+/// Send `signal` to `child`, without waiting for it to act on it.
+#[cfg(unix)]
+pub(crate) fn send(child: &tokio::process::Child, signal: Signal) -> io::Result<()> {
+    let pid = child
+        .id()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "process has already exited"))?;
+
+    nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid as i32), signal)
+        .map_err(|errno| io::Error::from_raw_os_error(errno as i32))
+}
+
+#[cfg(not(unix))]
+pub(crate) fn send(_child: &tokio::process::Child, _signal: Signal) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "sending arbitrary signals is only supported on unix",
+    ))
+}